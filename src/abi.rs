@@ -0,0 +1,810 @@
+//! Marshalling of guest/host ABI values to and from raw bytes.
+//!
+//! WASI hosts exchange these types with a Wasm guest by copying bytes into and out of the
+//! guest's linear memory, using the little-endian layout of the preview1 `#[repr(C)]`
+//! structs (the same layout the generated `wasi` crate reads via raw pointer casts). The
+//! [`AbiEncode`] and [`AbiDecode`] traits below reproduce that layout, including padding,
+//! without requiring a raw pointer on either side.
+
+use core::convert::TryFrom;
+
+use crate::{
+    Advice, AddressFamily, ClockId, Device, DirCookie, DirEnt, ErrNo, Event, EventFdState,
+    EventRwFlags, EventType, Fd, FdFlags, FdStat, FileStat, FileType, Inode, IoVec, LookupFlags,
+    OpenFlags, PreopenType, Prestat, RiFlags, Rights, RoFlags, SdFlags, SetTimeFlags, SiFlags,
+    Signal, SockAddr, SockAddrIn4, SockAddrIn6, SockType, Subscription, SubscriptionClock,
+    SubscriptionFdReadwrite, SubscriptionUnion, Timestamp, Whence,
+};
+
+/// Serializes a value to its little-endian preview1 ABI byte representation.
+pub trait AbiEncode: Sized {
+    /// The fixed size, in bytes, of this type's ABI encoding.
+    const ABI_SIZE: usize;
+
+    /// Writes `self`'s ABI encoding to the front of `out`, returning the number of bytes
+    /// written.
+    ///
+    /// Fails with `ErrNo::Overflow` if `out` is shorter than `Self::ABI_SIZE`.
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo>;
+}
+
+/// Deserializes a value from its little-endian preview1 ABI byte representation.
+pub trait AbiDecode: Sized {
+    /// Reads an ABI encoding of `Self` from the front of `bytes`.
+    ///
+    /// Fails with `ErrNo::Overflow` if `bytes` is shorter than `Self::ABI_SIZE`, or with
+    /// `ErrNo::Inval` if `bytes` does not encode a valid value.
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo>;
+}
+
+#[inline]
+fn check_len(len: usize, needed: usize) -> Result<(), ErrNo> {
+    if len < needed {
+        Err(ErrNo::Overflow)
+    } else {
+        Ok(())
+    }
+}
+
+macro_rules! impl_abi_for_int {
+    ($int:ty) => {
+        impl AbiEncode for $int {
+            const ABI_SIZE: usize = core::mem::size_of::<$int>();
+
+            fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+                check_len(out.len(), Self::ABI_SIZE)?;
+                out[..Self::ABI_SIZE].copy_from_slice(&self.to_le_bytes());
+                Ok(Self::ABI_SIZE)
+            }
+        }
+
+        impl AbiDecode for $int {
+            fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+                check_len(bytes.len(), Self::ABI_SIZE)?;
+                let mut buf = [0u8; core::mem::size_of::<$int>()];
+                buf.copy_from_slice(&bytes[..Self::ABI_SIZE]);
+                Ok(<$int>::from_le_bytes(buf))
+            }
+        }
+    };
+}
+
+impl_abi_for_int!(u8);
+impl_abi_for_int!(u16);
+impl_abi_for_int!(u32);
+impl_abi_for_int!(u64);
+impl_abi_for_int!(i64);
+
+/// Implements `AbiEncode`/`AbiDecode` for a fieldless, primitive-repr enum by round-tripping
+/// through its integer representation.
+macro_rules! impl_abi_for_prim_enum {
+    ($ty:ty, $int:ty) => {
+        impl AbiEncode for $ty {
+            const ABI_SIZE: usize = <$int as AbiEncode>::ABI_SIZE;
+
+            fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+                (*self as $int).encode(out)
+            }
+        }
+
+        impl AbiDecode for $ty {
+            fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+                let raw = <$int as AbiDecode>::decode(bytes)?;
+                <$ty>::try_from(raw).map_err(|_| ErrNo::Inval)
+            }
+        }
+    };
+}
+
+impl_abi_for_prim_enum!(AddressFamily, u8);
+impl_abi_for_prim_enum!(Advice, u8);
+impl_abi_for_prim_enum!(ClockId, u32);
+impl_abi_for_prim_enum!(ErrNo, u16);
+impl_abi_for_prim_enum!(EventType, u8);
+impl_abi_for_prim_enum!(EventRwFlags, u16);
+impl_abi_for_prim_enum!(FileType, u8);
+impl_abi_for_prim_enum!(Signal, u8);
+impl_abi_for_prim_enum!(SockType, u8);
+impl_abi_for_prim_enum!(Whence, u8);
+
+/// Implements `AbiEncode`/`AbiDecode` for a bitflags type by round-tripping through its raw
+/// `bits` representation, rejecting unrecognized bits via the existing `TryFrom` impl.
+macro_rules! impl_abi_for_bitflags {
+    ($ty:ty, $int:ty) => {
+        impl AbiEncode for $ty {
+            const ABI_SIZE: usize = <$int as AbiEncode>::ABI_SIZE;
+
+            fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+                self.bits.encode(out)
+            }
+        }
+
+        impl AbiDecode for $ty {
+            fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+                let raw = <$int as AbiDecode>::decode(bytes)?;
+                <$ty>::try_from(raw).map_err(|_| ErrNo::Inval)
+            }
+        }
+    };
+}
+
+impl_abi_for_bitflags!(FdFlags, u16);
+impl_abi_for_bitflags!(OpenFlags, u16);
+impl_abi_for_bitflags!(LookupFlags, u32);
+impl_abi_for_bitflags!(Rights, u64);
+impl_abi_for_bitflags!(SetTimeFlags, u16);
+impl_abi_for_bitflags!(SdFlags, u8);
+impl_abi_for_bitflags!(SiFlags, u16);
+impl_abi_for_bitflags!(RiFlags, u16);
+impl_abi_for_bitflags!(RoFlags, u16);
+
+/// Implements `AbiEncode`/`AbiDecode` for a `(pub) u64`/`u32` newtype wrapper.
+macro_rules! impl_abi_for_newtype {
+    ($ty:ident, $int:ty) => {
+        impl AbiEncode for $ty {
+            const ABI_SIZE: usize = <$int as AbiEncode>::ABI_SIZE;
+
+            fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+                self.0.encode(out)
+            }
+        }
+
+        impl AbiDecode for $ty {
+            fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+                <$int as AbiDecode>::decode(bytes).map($ty)
+            }
+        }
+    };
+}
+
+impl_abi_for_newtype!(Device, u64);
+impl_abi_for_newtype!(DirCookie, u64);
+impl_abi_for_newtype!(Inode, u64);
+impl_abi_for_newtype!(Fd, u32);
+
+impl AbiEncode for Timestamp {
+    const ABI_SIZE: usize = 8;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        self.as_nanos().encode(out)
+    }
+}
+
+impl AbiDecode for Timestamp {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        u64::decode(bytes).map(Timestamp::from_nanos)
+    }
+}
+
+/// A directory entry: `next: u64, inode: u64, name_len: u32, file_type: u8` followed by 3
+/// bytes of trailing padding, for 24 bytes total.
+impl AbiEncode for DirEnt {
+    const ABI_SIZE: usize = 24;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        self.next.encode(&mut out[0..8])?;
+        self.inode.encode(&mut out[8..16])?;
+        self.name_len.encode(&mut out[16..20])?;
+        self.file_type.encode(&mut out[20..21])?;
+        out[21..24].copy_from_slice(&[0; 3]);
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for DirEnt {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        Ok(DirEnt {
+            next: DirCookie::decode(&bytes[0..8])?,
+            inode: Inode::decode(&bytes[8..16])?,
+            name_len: u32::decode(&bytes[16..20])?,
+            file_type: FileType::decode(&bytes[20..21])?,
+        })
+    }
+}
+
+/// `file_type: u8` followed by a pad byte, `flags: u16`, a 4-byte pad, then two `u64` rights
+/// fields, for 24 bytes total.
+impl AbiEncode for FdStat {
+    const ABI_SIZE: usize = 24;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        self.file_type.encode(&mut out[0..1])?;
+        out[1] = 0;
+        self.flags.encode(&mut out[2..4])?;
+        out[4..8].copy_from_slice(&[0; 4]);
+        self.rights_base.encode(&mut out[8..16])?;
+        self.rights_inheriting.encode(&mut out[16..24])?;
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for FdStat {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        Ok(FdStat {
+            file_type: FileType::decode(&bytes[0..1])?,
+            flags: FdFlags::decode(&bytes[2..4])?,
+            rights_base: Rights::decode(&bytes[8..16])?,
+            rights_inheriting: Rights::decode(&bytes[16..24])?,
+        })
+    }
+}
+
+/// `device: u64, inode: u64, file_type: u8` (padded to 8), `num_links: u64`, `file_size: u64`,
+/// then three 8-byte timestamps, for 64 bytes total.
+impl AbiEncode for FileStat {
+    const ABI_SIZE: usize = 64;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        self.device.encode(&mut out[0..8])?;
+        self.inode.encode(&mut out[8..16])?;
+        self.file_type.encode(&mut out[16..17])?;
+        out[17..24].copy_from_slice(&[0; 7]);
+        self.num_links.encode(&mut out[24..32])?;
+        self.file_size.encode(&mut out[32..40])?;
+        self.atime.encode(&mut out[40..48])?;
+        self.mtime.encode(&mut out[48..56])?;
+        self.ctime.encode(&mut out[56..64])?;
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for FileStat {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        Ok(FileStat {
+            device: Device::decode(&bytes[0..8])?,
+            inode: Inode::decode(&bytes[8..16])?,
+            file_type: FileType::decode(&bytes[16..17])?,
+            num_links: u64::decode(&bytes[24..32])?,
+            file_size: u64::decode(&bytes[32..40])?,
+            atime: Timestamp::decode(&bytes[40..48])?,
+            mtime: Timestamp::decode(&bytes[48..56])?,
+            ctime: Timestamp::decode(&bytes[56..64])?,
+        })
+    }
+}
+
+/// Two `u32`s: `buf`, then `len`.
+impl AbiEncode for IoVec {
+    const ABI_SIZE: usize = 8;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        self.buf.encode(&mut out[0..4])?;
+        self.len.encode(&mut out[4..8])?;
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for IoVec {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        Ok(IoVec {
+            buf: u32::decode(&bytes[0..4])?,
+            len: u32::decode(&bytes[4..8])?,
+        })
+    }
+}
+
+/// A one-variant tagged union: `tag: u8` (always 0, for `Dir`), padded to 4, then
+/// `name_len: u32`, for 8 bytes total.
+impl AbiEncode for PreopenType {
+    const ABI_SIZE: usize = 8;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        let PreopenType::Dir { name_len } = self;
+        out[0] = 0;
+        out[1..4].copy_from_slice(&[0; 3]);
+        name_len.encode(&mut out[4..8])?;
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for PreopenType {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        match bytes[0] {
+            0 => Ok(PreopenType::Dir {
+                name_len: u32::decode(&bytes[4..8])?,
+            }),
+            _ => Err(ErrNo::Inval),
+        }
+    }
+}
+
+impl AbiEncode for Prestat {
+    const ABI_SIZE: usize = PreopenType::ABI_SIZE;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        self.resource_type.encode(out)
+    }
+}
+
+impl AbiDecode for Prestat {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        Ok(Prestat {
+            resource_type: PreopenType::decode(bytes)?,
+        })
+    }
+}
+
+/// `file_size: u64, flags: u16` padded to 16 bytes total.
+impl AbiEncode for EventFdState {
+    const ABI_SIZE: usize = 16;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        self.file_size.encode(&mut out[0..8])?;
+        self.flags.encode(&mut out[8..10])?;
+        out[10..16].copy_from_slice(&[0; 6]);
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for EventFdState {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        Ok(EventFdState {
+            file_size: u64::decode(&bytes[0..8])?,
+            flags: EventRwFlags::decode(&bytes[8..10])?,
+        })
+    }
+}
+
+/// `user_data: u64, error: u16, ty: u8` padded to 16, then a 16-byte `fd_state` region (valid
+/// only when `ty` is `FdRead`/`FdWrite`), for 32 bytes total.
+impl AbiEncode for Event {
+    const ABI_SIZE: usize = 32;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        self.user_data.encode(&mut out[0..8])?;
+        self.error.encode(&mut out[8..10])?;
+        self.ty.encode(&mut out[10..11])?;
+        out[11..16].copy_from_slice(&[0; 5]);
+        match &self.fd_state {
+            Some(fd_state) => {
+                fd_state.encode(&mut out[16..32])?;
+            }
+            None => out[16..32].copy_from_slice(&[0; 16]),
+        }
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for Event {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        let ty = EventType::decode(&bytes[10..11])?;
+        let fd_state = match ty {
+            EventType::Clock => None,
+            EventType::FdRead | EventType::FdWrite => Some(EventFdState::decode(&bytes[16..32])?),
+        };
+        Ok(Event {
+            user_data: u64::decode(&bytes[0..8])?,
+            error: ErrNo::decode(&bytes[8..10])?,
+            ty,
+            fd_state,
+        })
+    }
+}
+
+/// `clock_id: u32` padded to 8, `timeout: u64`, `precision: u64`, `flags: u16` padded to 32
+/// bytes total.
+impl AbiEncode for SubscriptionClock {
+    const ABI_SIZE: usize = 32;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        self.clock_id.encode(&mut out[0..4])?;
+        out[4..8].copy_from_slice(&[0; 4]);
+        self.timeout.encode(&mut out[8..16])?;
+        self.precision.encode(&mut out[16..24])?;
+        self.flags.encode(&mut out[24..26])?;
+        out[26..32].copy_from_slice(&[0; 6]);
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for SubscriptionClock {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        Ok(SubscriptionClock {
+            clock_id: ClockId::decode(&bytes[0..4])?,
+            timeout: Timestamp::decode(&bytes[8..16])?,
+            precision: Timestamp::decode(&bytes[16..24])?,
+            flags: u16::decode(&bytes[24..26])?,
+        })
+    }
+}
+
+/// A single `fd: u32`.
+impl AbiEncode for SubscriptionFdReadwrite {
+    const ABI_SIZE: usize = 4;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        self.fd.encode(out)
+    }
+}
+
+impl AbiDecode for SubscriptionFdReadwrite {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        Ok(SubscriptionFdReadwrite {
+            fd: Fd::decode(bytes)?,
+        })
+    }
+}
+
+/// `tag: u8` (0 = `Clock`, 1 = `FdRead`, 2 = `FdWrite`), padded to 8, followed by a 32-byte
+/// payload sized to the largest variant (`SubscriptionClock`), for 40 bytes total.
+impl AbiEncode for SubscriptionUnion {
+    const ABI_SIZE: usize = 40;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        out[1..8].copy_from_slice(&[0; 7]);
+        match self {
+            SubscriptionUnion::Clock(clock) => {
+                out[0] = 0;
+                clock.encode(&mut out[8..40])?;
+            }
+            SubscriptionUnion::FdRead(fd_readwrite) => {
+                out[0] = 1;
+                fd_readwrite.encode(&mut out[8..12])?;
+                out[12..40].copy_from_slice(&[0; 28]);
+            }
+            SubscriptionUnion::FdWrite(fd_readwrite) => {
+                out[0] = 2;
+                fd_readwrite.encode(&mut out[8..12])?;
+                out[12..40].copy_from_slice(&[0; 28]);
+            }
+        }
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for SubscriptionUnion {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        match bytes[0] {
+            0 => Ok(SubscriptionUnion::Clock(SubscriptionClock::decode(&bytes[8..40])?)),
+            1 => Ok(SubscriptionUnion::FdRead(SubscriptionFdReadwrite::decode(
+                &bytes[8..12],
+            )?)),
+            2 => Ok(SubscriptionUnion::FdWrite(SubscriptionFdReadwrite::decode(
+                &bytes[8..12],
+            )?)),
+            _ => Err(ErrNo::Inval),
+        }
+    }
+}
+
+/// `userdata: u64` followed by the 40-byte `u` union, for 48 bytes total.
+impl AbiEncode for Subscription {
+    const ABI_SIZE: usize = 48;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        self.userdata.encode(&mut out[0..8])?;
+        self.u.encode(&mut out[8..48])?;
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for Subscription {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        Ok(Subscription {
+            userdata: u64::decode(&bytes[0..8])?,
+            u: SubscriptionUnion::decode(&bytes[8..48])?,
+        })
+    }
+}
+
+/// Serializes a stream of directory entries into `out`, following the `fd_readdir` wire
+/// format: each entry's `DirEnt` header immediately followed by its name's raw UTF-8 bytes,
+/// repeated for every `(DirEnt, name)` pair.
+///
+/// If `out` fills before an entry (or its name) can be written in full, that entry is
+/// truncated mid-struct or mid-name -- the WASI convention for signalling "buffer full, call
+/// `fd_readdir` again" -- and no further entries are written.
+///
+/// Returns the number of bytes written.
+pub fn write_dirents<'a>(
+    entries: impl Iterator<Item = (DirEnt, &'a str)>,
+    out: &mut [u8],
+) -> usize {
+    let mut written = 0;
+    let mut header = [0u8; DirEnt::ABI_SIZE];
+    for (dirent, name) in entries {
+        if written == out.len() {
+            break;
+        }
+
+        dirent
+            .encode(&mut header)
+            .expect("header is exactly DirEnt::ABI_SIZE long");
+        let header_len = (out.len() - written).min(header.len());
+        out[written..written + header_len].copy_from_slice(&header[..header_len]);
+        written += header_len;
+        if header_len < header.len() {
+            break;
+        }
+
+        let name_bytes = name.as_bytes();
+        let name_len = (out.len() - written).min(name_bytes.len());
+        out[written..written + name_len].copy_from_slice(&name_bytes[..name_len]);
+        written += name_len;
+        if name_len < name_bytes.len() {
+            break;
+        }
+    }
+    written
+}
+
+/// A 4-byte address followed by a 2-byte port, for 6 bytes total.
+impl AbiEncode for SockAddrIn4 {
+    const ABI_SIZE: usize = 6;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        out[0..4].copy_from_slice(&self.addr);
+        self.port.encode(&mut out[4..6])?;
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for SockAddrIn4 {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        let mut addr = [0u8; 4];
+        addr.copy_from_slice(&bytes[0..4]);
+        Ok(SockAddrIn4 {
+            addr,
+            port: u16::decode(&bytes[4..6])?,
+        })
+    }
+}
+
+/// A 16-byte address followed by a 2-byte port, for 18 bytes total.
+impl AbiEncode for SockAddrIn6 {
+    const ABI_SIZE: usize = 18;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        out[0..16].copy_from_slice(&self.addr);
+        self.port.encode(&mut out[16..18])?;
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for SockAddrIn6 {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        let mut addr = [0u8; 16];
+        addr.copy_from_slice(&bytes[0..16]);
+        Ok(SockAddrIn6 {
+            addr,
+            port: u16::decode(&bytes[16..18])?,
+        })
+    }
+}
+
+/// A one-byte `AddressFamily` tag, padded to 2, followed by a payload region sized to the
+/// largest variant (`SockAddrIn6`), for 20 bytes total.
+impl AbiEncode for SockAddr {
+    const ABI_SIZE: usize = 20;
+
+    fn encode(&self, out: &mut [u8]) -> Result<usize, ErrNo> {
+        check_len(out.len(), Self::ABI_SIZE)?;
+        out[1] = 0;
+        match self {
+            SockAddr::Inet4(addr) => {
+                out[0] = AddressFamily::Inet4 as u8;
+                addr.encode(&mut out[2..8])?;
+                out[8..20].copy_from_slice(&[0; 12]);
+            }
+            SockAddr::Inet6(addr) => {
+                out[0] = AddressFamily::Inet6 as u8;
+                addr.encode(&mut out[2..20])?;
+            }
+            SockAddr::Unix => {
+                out[0] = AddressFamily::Unix as u8;
+                out[2..20].copy_from_slice(&[0; 18]);
+            }
+        }
+        Ok(Self::ABI_SIZE)
+    }
+}
+
+impl AbiDecode for SockAddr {
+    fn decode(bytes: &[u8]) -> Result<Self, ErrNo> {
+        check_len(bytes.len(), Self::ABI_SIZE)?;
+        match AddressFamily::decode(&bytes[0..1])? {
+            AddressFamily::Inet4 => Ok(SockAddr::Inet4(SockAddrIn4::decode(&bytes[2..8])?)),
+            AddressFamily::Inet6 => Ok(SockAddr::Inet6(SockAddrIn6::decode(&bytes[2..20])?)),
+            AddressFamily::Unix => Ok(SockAddr::Unix),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClockId, EventType, Fd, FdFlags, FileType, Rights};
+
+    fn round_trip<T: AbiEncode + AbiDecode + PartialEq + core::fmt::Debug>(value: T) {
+        let mut buf = [0u8; 64];
+        let written = value.encode(&mut buf).unwrap();
+        assert_eq!(written, T::ABI_SIZE);
+        assert_eq!(T::decode(&buf[..written]).unwrap(), value);
+    }
+
+    #[test]
+    fn dirent_round_trips() {
+        round_trip(DirEnt {
+            next: DirCookie(1),
+            inode: Inode(2),
+            name_len: 3,
+            file_type: FileType::RegularFile,
+        });
+    }
+
+    #[test]
+    fn fdstat_round_trips() {
+        round_trip(FdStat {
+            file_type: FileType::CharacterDevice,
+            flags: FdFlags::NONBLOCK | FdFlags::APPEND,
+            rights_base: Rights::FD_READ | Rights::FD_WRITE,
+            rights_inheriting: Rights::PATH_OPEN,
+        });
+    }
+
+    #[test]
+    fn filestat_round_trips() {
+        round_trip(FileStat {
+            device: Device::from(1u64),
+            inode: Inode(2),
+            file_type: FileType::RegularFile,
+            num_links: 3,
+            file_size: 4,
+            atime: Timestamp::from_nanos(5),
+            mtime: Timestamp::from_nanos(6),
+            ctime: Timestamp::from_nanos(7),
+        });
+    }
+
+    #[test]
+    fn event_round_trips_with_fd_state() {
+        round_trip(Event {
+            user_data: 42,
+            error: ErrNo::Success,
+            ty: EventType::FdRead,
+            fd_state: Some(EventFdState {
+                file_size: 123,
+                flags: EventRwFlags::Hangup,
+            }),
+        });
+    }
+
+    #[test]
+    fn event_round_trips_without_fd_state() {
+        round_trip(Event {
+            user_data: 42,
+            error: ErrNo::Success,
+            ty: EventType::Clock,
+            fd_state: None,
+        });
+    }
+
+    #[test]
+    fn subscription_round_trips() {
+        let sub = Subscription {
+            userdata: 7,
+            u: SubscriptionUnion::Clock(SubscriptionClock {
+                clock_id: ClockId::Monotonic,
+                timeout: Timestamp::from_nanos(8),
+                precision: Timestamp::from_nanos(9),
+                flags: 0,
+            }),
+        };
+        let mut buf = [0u8; Subscription::ABI_SIZE];
+        sub.encode(&mut buf).unwrap();
+        let decoded = Subscription::decode(&buf).unwrap();
+        assert_eq!(decoded.userdata, sub.userdata);
+        match (decoded.u, sub.u) {
+            (SubscriptionUnion::Clock(got), SubscriptionUnion::Clock(want)) => {
+                assert_eq!(got.clock_id, want.clock_id);
+                assert_eq!(got.timeout, want.timeout);
+                assert_eq!(got.precision, want.precision);
+                assert_eq!(got.flags, want.flags);
+            }
+            _ => panic!("decoded variant didn't match encoded variant"),
+        }
+
+        let sub = Subscription {
+            userdata: 7,
+            u: SubscriptionUnion::FdWrite(SubscriptionFdReadwrite { fd: Fd(3) }),
+        };
+        let mut buf = [0u8; Subscription::ABI_SIZE];
+        sub.encode(&mut buf).unwrap();
+        match Subscription::decode(&buf).unwrap().u {
+            SubscriptionUnion::FdWrite(fd_readwrite) => assert_eq!(fd_readwrite.fd, Fd(3)),
+            other => panic!("expected FdWrite, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn sockaddr_round_trips() {
+        round_trip(SockAddr::Inet4(SockAddrIn4 {
+            addr: [127, 0, 0, 1],
+            port: 8080,
+        }));
+        round_trip(SockAddr::Inet6(SockAddrIn6 {
+            addr: [0; 16],
+            port: 443,
+        }));
+        round_trip(SockAddr::Unix);
+    }
+
+    #[test]
+    fn write_dirents_fits_whole_entries() {
+        let entries = [
+            (
+                DirEnt {
+                    next: DirCookie(1),
+                    inode: Inode(1),
+                    name_len: 1,
+                    file_type: FileType::RegularFile,
+                },
+                "a",
+            ),
+            (
+                DirEnt {
+                    next: DirCookie(2),
+                    inode: Inode(2),
+                    name_len: 2,
+                    file_type: FileType::Directory,
+                },
+                "bb",
+            ),
+        ];
+        let mut out = [0u8; 2 * (DirEnt::ABI_SIZE + 2)];
+        let written = write_dirents(entries.iter().copied(), &mut out);
+        assert_eq!(written, 2 * DirEnt::ABI_SIZE + 1 + 2);
+
+        let first_end = DirEnt::ABI_SIZE + 1;
+        assert_eq!(
+            DirEnt::decode(&out[..DirEnt::ABI_SIZE]).unwrap(),
+            DirEnt {
+                next: DirCookie(1),
+                inode: Inode(1),
+                name_len: 1,
+                file_type: FileType::RegularFile,
+            }
+        );
+        assert_eq!(&out[DirEnt::ABI_SIZE..first_end], b"a");
+    }
+
+    #[test]
+    fn write_dirents_truncates_when_out_is_too_small() {
+        let entries = [(
+            DirEnt {
+                next: DirCookie(1),
+                inode: Inode(1),
+                name_len: 4,
+                file_type: FileType::RegularFile,
+            },
+            "name",
+        )];
+        let mut out = [0u8; 10];
+        let written = write_dirents(entries.iter().copied(), &mut out);
+        assert_eq!(written, out.len());
+    }
+}
@@ -1,8 +1,11 @@
 //! Rusty WASI type definitions based on
 //! [the spec](https://github.com/CraneStation/wasmtime/blob/master/docs/WASI-api.md)
+//!
+//! This crate is `#![no_std]` unless the (default-enabled) `std` feature is active, so the
+//! type definitions can be used in embedded, Redox, or kernel contexts without dragging in
+//! `std`.
 
-
-#![cfg_attr(feature = "sgx", no_std)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 #[cfg(feature = "sgx")]
 #[macro_use]
@@ -12,11 +15,13 @@ extern crate sgx_tstd as std;
 extern crate bitflags;
 #[macro_use]
 extern crate proper;
-use std::cmp::Ordering;
-use std::convert::TryFrom;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
 use serde::{Deserialize, Serialize};
 use err_derive::Error;
 
+pub mod abi;
+
 /// File or memory access pattern advisory information.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Prim, Debug)]
@@ -47,6 +52,42 @@ impl From<Advice> for u8 {
     }
 }
 
+impl Advice {
+    /// This advice's short identifier, e.g. `"Sequential"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Advice::Normal => "Normal",
+            Advice::Sequential => "Sequential",
+            Advice::Random => "Random",
+            Advice::DontNeed => "DontNeed",
+            Advice::NoReuse => "NoReuse",
+            Advice::WillNeed => "WillNeed",
+        }
+    }
+
+    /// A human-readable description of this advice.
+    #[inline]
+    pub const fn message(&self) -> &'static str {
+        match self {
+            Advice::Normal => {
+                "The application has no advice to give on its behavior with respect to the specified data."
+            }
+            Advice::Sequential => {
+                "The application expects to access the data sequentially from lower to higher offsets."
+            }
+            Advice::Random => "The application expects to access the specified data in a random order.",
+            Advice::DontNeed => {
+                "The application expects that it will not access the specified data in the near future."
+            }
+            Advice::NoReuse => {
+                "The application expects to access the specified data once and then not reuse it thereafter."
+            }
+            Advice::WillNeed => "The application expects to access the specified data in the near future.",
+        }
+    }
+}
+
 /// Identifiers for clocks.
 #[repr(u32)]
 #[prim(ty = "u32")]
@@ -76,6 +117,36 @@ impl From<ClockId> for u32 {
     }
 }
 
+impl ClockId {
+    /// This clock's short identifier, e.g. `"Monotonic"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            ClockId::RealTime => "RealTime",
+            ClockId::Monotonic => "Monotonic",
+            ClockId::ProcessCpuTime => "ProcessCpuTime",
+            ClockId::ThreadCpuTime => "ThreadCpuTime",
+        }
+    }
+
+    /// A human-readable description of this clock.
+    #[inline]
+    pub const fn message(&self) -> &'static str {
+        match self {
+            ClockId::RealTime => {
+                "The clock measuring real time. Time value zero corresponds with 1970-01-01T00:00:00Z."
+            }
+            ClockId::Monotonic => {
+                "The store-wide monotonic clock, which is defined as a clock measuring real time, whose \
+                 value cannot be adjusted and which cannot have negative clock jumps. The epoch of this \
+                 clock is undefined. The absolute time value of this clock therefore has no meaning."
+            }
+            ClockId::ProcessCpuTime => "The CPU-time clock associated with the current process.",
+            ClockId::ThreadCpuTime => "The CPU-time clock associated with the current thread.",
+        }
+    }
+}
+
 /// Identifier for a device containing a file system. Can be used in combination with `Inode`
 /// to uniquely identify a file or directory in the filesystem.
 #[repr(C)]
@@ -427,6 +498,111 @@ pub enum ErrNo {
     NotCapable,
 }
 
+impl ErrNo {
+    /// This error's short identifier, e.g. `"NoEnt"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name_and_message().0
+    }
+
+    /// A human-readable description of this error.
+    #[inline]
+    pub const fn message(&self) -> &'static str {
+        self.name_and_message().1
+    }
+
+    const fn name_and_message(&self) -> (&'static str, &'static str) {
+        match self {
+            ErrNo::Success => ("Success", "No error occurred. System call completed successfully."),
+            ErrNo::TooBig => ("TooBig", "Argument list too long."),
+            ErrNo::Access => ("Access", "Permission denied."),
+            ErrNo::AddrInUse => ("AddrInUse", "Address in use."),
+            ErrNo::AddrNotAvail => ("AddrNotAvail", "Address not available."),
+            ErrNo::AfNoSupport => ("AfNoSupport", "Address family not supported."),
+            ErrNo::Again => ("Again", "Resource unavailable, or operation would block."),
+            ErrNo::Already => ("Already", "Connection already in progress."),
+            ErrNo::BadF => ("BadF", "Bad file descriptor."),
+            ErrNo::BadMsg => ("BadMsg", "Bad message."),
+            ErrNo::Busy => ("Busy", "Device or resource busy."),
+            ErrNo::Canceled => ("Canceled", "Operation canceled."),
+            ErrNo::Child => ("Child", "No child processes."),
+            ErrNo::ConnAborted => ("ConnAborted", "Connection aborted."),
+            ErrNo::ConnRefused => ("ConnRefused", "Connection refused."),
+            ErrNo::ConnReset => ("ConnReset", "Connection reset."),
+            ErrNo::Deadlk => ("Deadlk", "Resource deadlock would occur."),
+            ErrNo::DestAddrReq => ("DestAddrReq", "Destination address required."),
+            ErrNo::Domain => ("Domain", "Mathematics argument out of domain of function."),
+            ErrNo::DQuot => ("DQuot", "Reserved. (Quota exceeded.)"),
+            ErrNo::Exist => ("Exist", "File exists."),
+            ErrNo::Fault => ("Fault", "Bad address."),
+            ErrNo::FBig => ("FBig", "File too large."),
+            ErrNo::HostUnreach => ("HostUnreach", "Host is unreachable."),
+            ErrNo::IdRm => ("IdRm", "Identifier removed."),
+            ErrNo::IlSeq => ("IlSeq", "Illegal byte sequence."),
+            ErrNo::InProgress => ("InProgress", "Operation in progress."),
+            ErrNo::Intr => ("Intr", "Interrupted function."),
+            ErrNo::Inval => ("Inval", "Invalid argument."),
+            ErrNo::Io => ("Io", "I/O error."),
+            ErrNo::IsConn => ("IsConn", "Socket is connected."),
+            ErrNo::IsDir => ("IsDir", "Is a directory."),
+            ErrNo::Loop => ("Loop", "Too many levels of symbolic links."),
+            ErrNo::MFile => ("MFile", "File descriptor value too large."),
+            ErrNo::MLink => ("MLink", "Too many links."),
+            ErrNo::MsgSize => ("MsgSize", "Message too large."),
+            ErrNo::Multihop => ("Multihop", "Reserved. (Multihop attempted.)"),
+            ErrNo::NameTooLong => ("NameTooLong", "Filename too long."),
+            ErrNo::NetDown => ("NetDown", "Network is down."),
+            ErrNo::NetReset => ("NetReset", "Connection aborted by network."),
+            ErrNo::NetUnreach => ("NetUnreach", "Network unreachable."),
+            ErrNo::NFile => ("NFile", "Too many files open in system."),
+            ErrNo::NoBufS => ("NoBufS", "No buffer space available."),
+            ErrNo::NoDev => ("NoDev", "No such device."),
+            ErrNo::NoEnt => ("NoEnt", "No such file or directory."),
+            ErrNo::NoExec => ("NoExec", "Executable file format error."),
+            ErrNo::NoLock => ("NoLock", "No locks available."),
+            ErrNo::NoLink => ("NoLink", "Reserved. (Link has been severed.)"),
+            ErrNo::NoMem => ("NoMem", "Not enough space."),
+            ErrNo::NoMsg => ("NoMsg", "No message of the desired type."),
+            ErrNo::NoProtoOpt => ("NoProtoOpt", "Protocol not available."),
+            ErrNo::NoSpace => ("NoSpace", "No space left on device."),
+            ErrNo::NoSys => ("NoSys", "Function not supported. (Always unsupported.)"),
+            ErrNo::NotConn => ("NotConn", "The socket is not connected."),
+            ErrNo::NotDir => ("NotDir", "Not a directory or a symbolic link to a directory."),
+            ErrNo::NotEmpty => ("NotEmpty", "Directory not empty."),
+            ErrNo::NotRecoverable => ("NotRecoverable", "State not recoverable."),
+            ErrNo::NotSock => ("NotSock", "Not a socket."),
+            ErrNo::NotSup => ("NotSup", "Not supported, or operation not supported on socket. (Transient unsupported.)"),
+            ErrNo::NoTty => ("NoTty", "Inappropriate I/O control operation."),
+            ErrNo::NxIo => ("NxIo", "No such device or address."),
+            ErrNo::Overflow => ("Overflow", "Value too large to be stored in data type."),
+            ErrNo::OwnerDead => ("OwnerDead", "Previous owner died."),
+            ErrNo::Perm => ("Perm", "Operation not permitted."),
+            ErrNo::Pipe => ("Pipe", "Broken pipe."),
+            ErrNo::Proto => ("Proto", "Protocol error."),
+            ErrNo::ProtoNoSupport => ("ProtoNoSupport", "Protocol not supported."),
+            ErrNo::ProtoType => ("ProtoType", "Protocol wrong type for socket."),
+            ErrNo::Range => ("Range", "Result too large."),
+            ErrNo::RoFs => ("RoFs", "Read-only file system."),
+            ErrNo::SPipe => ("SPipe", "Invalid seek."),
+            ErrNo::Srch => ("Srch", "No such process."),
+            ErrNo::Stale => ("Stale", "Reserved. (Stale file handle.)"),
+            ErrNo::TimedOut => ("TimedOut", "Connection timed out."),
+            ErrNo::TxtBsy => ("TxtBsy", "Text file busy."),
+            ErrNo::XDev => ("XDev", "Cross-device link."),
+            ErrNo::NotCapable => ("NotCapable", "Extension: Capabilities insufficient."),
+        }
+    }
+}
+
+/// Looks up the description of a raw `ErrNo` value (see `ErrNo::message`), returning
+/// `"Unknown error"` if `code` is not a valid `ErrNo`.
+pub fn strerror(code: u16) -> &'static str {
+    match ErrNo::try_from(code) {
+        Ok(errno) => errno.message(),
+        Err(_) => "Unknown error",
+    }
+}
+
 impl From<ErrNo> for u16 {
     #[inline]
     fn from(errno: ErrNo) -> Self {
@@ -434,6 +610,7 @@ impl From<ErrNo> for u16 {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for ErrNo {
     fn from(err: std::io::Error) -> Self {
         use std::io::ErrorKind;
@@ -459,6 +636,299 @@ impl From<std::io::Error> for ErrNo {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<ErrNo> for std::io::Error {
+    fn from(errno: ErrNo) -> Self {
+        use std::io::ErrorKind;
+        let kind = match errno {
+            ErrNo::NoEnt => ErrorKind::NotFound,
+            ErrNo::Access | ErrNo::Perm => ErrorKind::PermissionDenied,
+            ErrNo::ConnRefused => ErrorKind::ConnectionRefused,
+            ErrNo::ConnReset => ErrorKind::ConnectionReset,
+            ErrNo::ConnAborted => ErrorKind::ConnectionAborted,
+            ErrNo::NotConn => ErrorKind::NotConnected,
+            ErrNo::AddrInUse => ErrorKind::AddrInUse,
+            ErrNo::AddrNotAvail => ErrorKind::AddrNotAvailable,
+            ErrNo::Pipe => ErrorKind::BrokenPipe,
+            ErrNo::Exist => ErrorKind::AlreadyExists,
+            ErrNo::Again => ErrorKind::WouldBlock,
+            ErrNo::Inval => ErrorKind::InvalidInput,
+            ErrNo::TimedOut => ErrorKind::TimedOut,
+            ErrNo::Intr => ErrorKind::Interrupted,
+            _ => ErrorKind::Other,
+        };
+        std::io::Error::from(kind)
+    }
+}
+
+impl ErrNo {
+    /// Translates a raw OS errno number (in the Linux numbering, as glibc and musl define it)
+    /// into an `ErrNo`, or `None` if `code` doesn't correspond to one of the errors this enum
+    /// represents. The numbering is the same on every target; the differing numbers of other
+    /// Unix-like platforms (e.g. the BSDs and macOS) are not handled.
+    pub fn from_raw_os_error(code: i32) -> Option<Self> {
+        Some(match code {
+            1 => ErrNo::Perm,
+            2 => ErrNo::NoEnt,
+            3 => ErrNo::Srch,
+            4 => ErrNo::Intr,
+            5 => ErrNo::Io,
+            6 => ErrNo::NxIo,
+            7 => ErrNo::TooBig,
+            8 => ErrNo::NoExec,
+            9 => ErrNo::BadF,
+            10 => ErrNo::Child,
+            11 => ErrNo::Again,
+            12 => ErrNo::NoMem,
+            13 => ErrNo::Access,
+            14 => ErrNo::Fault,
+            16 => ErrNo::Busy,
+            17 => ErrNo::Exist,
+            18 => ErrNo::XDev,
+            19 => ErrNo::NoDev,
+            20 => ErrNo::NotDir,
+            21 => ErrNo::IsDir,
+            22 => ErrNo::Inval,
+            23 => ErrNo::NFile,
+            24 => ErrNo::MFile,
+            25 => ErrNo::NoTty,
+            26 => ErrNo::TxtBsy,
+            27 => ErrNo::FBig,
+            28 => ErrNo::NoSpace,
+            29 => ErrNo::SPipe,
+            30 => ErrNo::RoFs,
+            31 => ErrNo::MLink,
+            32 => ErrNo::Pipe,
+            33 => ErrNo::Domain,
+            34 => ErrNo::Range,
+            35 => ErrNo::Deadlk,
+            36 => ErrNo::NameTooLong,
+            37 => ErrNo::NoLock,
+            38 => ErrNo::NoSys,
+            39 => ErrNo::NotEmpty,
+            40 => ErrNo::Loop,
+            42 => ErrNo::NoMsg,
+            43 => ErrNo::IdRm,
+            67 => ErrNo::NoLink,
+            71 => ErrNo::Proto,
+            72 => ErrNo::Multihop,
+            74 => ErrNo::BadMsg,
+            75 => ErrNo::Overflow,
+            84 => ErrNo::IlSeq,
+            88 => ErrNo::NotSock,
+            89 => ErrNo::DestAddrReq,
+            90 => ErrNo::MsgSize,
+            91 => ErrNo::ProtoType,
+            92 => ErrNo::NoProtoOpt,
+            93 => ErrNo::ProtoNoSupport,
+            95 => ErrNo::NotSup,
+            97 => ErrNo::AfNoSupport,
+            98 => ErrNo::AddrInUse,
+            99 => ErrNo::AddrNotAvail,
+            100 => ErrNo::NetDown,
+            101 => ErrNo::NetUnreach,
+            102 => ErrNo::NetReset,
+            103 => ErrNo::ConnAborted,
+            104 => ErrNo::ConnReset,
+            105 => ErrNo::NoBufS,
+            106 => ErrNo::IsConn,
+            107 => ErrNo::NotConn,
+            110 => ErrNo::TimedOut,
+            111 => ErrNo::ConnRefused,
+            113 => ErrNo::HostUnreach,
+            114 => ErrNo::Already,
+            115 => ErrNo::InProgress,
+            116 => ErrNo::Stale,
+            122 => ErrNo::DQuot,
+            125 => ErrNo::Canceled,
+            130 => ErrNo::OwnerDead,
+            131 => ErrNo::NotRecoverable,
+            _ => return None,
+        })
+    }
+
+    /// Translates this `ErrNo` into a raw OS errno number (in the same Linux numbering), the
+    /// reverse of `ErrNo::from_raw_os_error`.
+    ///
+    /// `ErrNo::NotCapable` has no POSIX equivalent, since it's a WASI extension; it's mapped
+    /// to `EPERM`, the closest approximation of "insufficient capabilities". `ErrNo::Success`
+    /// is mapped to `0`, which `ErrNo::from_raw_os_error` does not accept.
+    pub fn to_raw_os_error(self) -> i32 {
+        match self {
+            ErrNo::Success => 0,
+            ErrNo::Perm | ErrNo::NotCapable => 1,
+            ErrNo::NoEnt => 2,
+            ErrNo::Srch => 3,
+            ErrNo::Intr => 4,
+            ErrNo::Io => 5,
+            ErrNo::NxIo => 6,
+            ErrNo::TooBig => 7,
+            ErrNo::NoExec => 8,
+            ErrNo::BadF => 9,
+            ErrNo::Child => 10,
+            ErrNo::Again => 11,
+            ErrNo::NoMem => 12,
+            ErrNo::Access => 13,
+            ErrNo::Fault => 14,
+            ErrNo::Busy => 16,
+            ErrNo::Exist => 17,
+            ErrNo::XDev => 18,
+            ErrNo::NoDev => 19,
+            ErrNo::NotDir => 20,
+            ErrNo::IsDir => 21,
+            ErrNo::Inval => 22,
+            ErrNo::NFile => 23,
+            ErrNo::MFile => 24,
+            ErrNo::NoTty => 25,
+            ErrNo::TxtBsy => 26,
+            ErrNo::FBig => 27,
+            ErrNo::NoSpace => 28,
+            ErrNo::SPipe => 29,
+            ErrNo::RoFs => 30,
+            ErrNo::MLink => 31,
+            ErrNo::Pipe => 32,
+            ErrNo::Domain => 33,
+            ErrNo::Range => 34,
+            ErrNo::Deadlk => 35,
+            ErrNo::NameTooLong => 36,
+            ErrNo::NoLock => 37,
+            ErrNo::NoSys => 38,
+            ErrNo::NotEmpty => 39,
+            ErrNo::Loop => 40,
+            ErrNo::NoMsg => 42,
+            ErrNo::IdRm => 43,
+            ErrNo::NoLink => 67,
+            ErrNo::Proto => 71,
+            ErrNo::Multihop => 72,
+            ErrNo::BadMsg => 74,
+            ErrNo::Overflow => 75,
+            ErrNo::IlSeq => 84,
+            ErrNo::NotSock => 88,
+            ErrNo::DestAddrReq => 89,
+            ErrNo::MsgSize => 90,
+            ErrNo::ProtoType => 91,
+            ErrNo::NoProtoOpt => 92,
+            ErrNo::ProtoNoSupport => 93,
+            ErrNo::NotSup => 95,
+            ErrNo::AfNoSupport => 97,
+            ErrNo::AddrInUse => 98,
+            ErrNo::AddrNotAvail => 99,
+            ErrNo::NetDown => 100,
+            ErrNo::NetUnreach => 101,
+            ErrNo::NetReset => 102,
+            ErrNo::ConnAborted => 103,
+            ErrNo::ConnReset => 104,
+            ErrNo::NoBufS => 105,
+            ErrNo::IsConn => 106,
+            ErrNo::NotConn => 107,
+            ErrNo::TimedOut => 110,
+            ErrNo::ConnRefused => 111,
+            ErrNo::HostUnreach => 113,
+            ErrNo::Already => 114,
+            ErrNo::InProgress => 115,
+            ErrNo::Stale => 116,
+            ErrNo::DQuot => 122,
+            ErrNo::Canceled => 125,
+            ErrNo::OwnerDead => 130,
+            ErrNo::NotRecoverable => 131,
+        }
+    }
+}
+
+#[cfg(test)]
+mod errno_raw_os_tests {
+    use super::ErrNo;
+
+    /// Known glibc errno values, spot-checked against `errno.h`.
+    const GLIBC_FIXED_POINTS: &[(i32, ErrNo)] = &[
+        (1, ErrNo::Perm),
+        (2, ErrNo::NoEnt),
+        (9, ErrNo::BadF),
+        (11, ErrNo::Again),
+        (22, ErrNo::Inval),
+        (38, ErrNo::NoSys),
+        (110, ErrNo::TimedOut),
+        (131, ErrNo::NotRecoverable),
+    ];
+
+    #[test]
+    fn from_raw_os_error_matches_known_glibc_values() {
+        for (code, errno) in GLIBC_FIXED_POINTS {
+            assert_eq!(ErrNo::from_raw_os_error(*code), Some(*errno));
+        }
+    }
+
+    #[test]
+    fn from_raw_os_error_rejects_unknown_codes() {
+        assert_eq!(ErrNo::from_raw_os_error(-1), None);
+        assert_eq!(ErrNo::from_raw_os_error(0), None);
+    }
+
+    #[test]
+    fn to_raw_os_error_round_trips_through_from_raw_os_error() {
+        for (code, errno) in GLIBC_FIXED_POINTS {
+            assert_eq!(errno.to_raw_os_error(), *code);
+            assert_eq!(ErrNo::from_raw_os_error(errno.to_raw_os_error()), Some(*errno));
+        }
+    }
+
+    #[test]
+    fn not_capable_maps_to_eperm_with_no_reverse_equivalent() {
+        assert_eq!(ErrNo::NotCapable.to_raw_os_error(), 1);
+        assert_eq!(ErrNo::from_raw_os_error(1), Some(ErrNo::Perm));
+    }
+}
+
+/// A `Result` alias using the niche-optimized [`Error`] as its error type by default.
+///
+/// The error type is a second, defaulted parameter (rather than being hard-coded) so that
+/// existing two-argument `Result<T, E>` usage elsewhere in this crate — including the
+/// `TryFrom` impls `#[derive(Prim)]` generates — keeps working unchanged.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// A non-success [`ErrNo`], represented as a `NonZeroU16` so that `Option<Error>` is the same
+/// size as a bare `u16`. `ErrNo` itself can't have this niche, since `Success` is a legitimate
+/// variant; this gives host code an ergonomic success-or-error type without taking that
+/// variant away.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Error(core::num::NonZeroU16);
+
+impl Error {
+    /// Wraps `errno` into an `Error`, or returns `None` if `errno` is `ErrNo::Success`.
+    #[inline]
+    pub fn from_errno(errno: ErrNo) -> Option<Self> {
+        core::num::NonZeroU16::new(u16::from(errno)).map(Error)
+    }
+
+    /// The `ErrNo` this `Error` wraps.
+    #[inline]
+    pub fn errno(&self) -> ErrNo {
+        ErrNo::try_from(self.0.get()).expect("Error only ever wraps a valid ErrNo")
+    }
+
+    /// The raw `u16` representation of this `Error`'s `ErrNo`.
+    #[inline]
+    pub fn raw(&self) -> u16 {
+        self.0.get()
+    }
+}
+
+impl core::fmt::Debug for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.errno(), f)
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.errno(), f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Event {
     pub user_data: UserData,
@@ -490,6 +960,37 @@ impl From<EventType> for u8 {
     }
 }
 
+impl EventType {
+    /// This event type's short identifier, e.g. `"FdRead"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            EventType::Clock => "Clock",
+            EventType::FdRead => "FdRead",
+            EventType::FdWrite => "FdWrite",
+        }
+    }
+
+    /// A human-readable description of this event type.
+    #[inline]
+    pub const fn message(&self) -> &'static str {
+        match self {
+            EventType::Clock => {
+                "The time value of clock `SubscriptionType::clock.clock_id` has reached timestamp \
+                 `Subscription::clock.timeout`."
+            }
+            EventType::FdRead => {
+                "File descriptor `SubscriptionType::FdRw.fd` has data available for reading. This \
+                 event always triggers for regular files."
+            }
+            EventType::FdWrite => {
+                "File descriptor `SubscriptionType::FdRw.fd` has capacity available for writing. \
+                 This event always triggers for regular files."
+            }
+        }
+    }
+}
+
 /// The state of the file descriptor subscribed to with `EventType::FdRead` or `EventType::FdWrte`.
 #[repr(u16)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Prim)]
@@ -628,13 +1129,29 @@ pub type FileDelta = i64;
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Prim)]
 pub enum FileType {
+    /// The type of the file descriptor or file is unknown or is different from any of the
+    /// other types specified.
     Unknown,
+
+    /// The file descriptor or file refers to a block device inode.
     BlockDevice,
+
+    /// The file descriptor or file refers to a character device inode.
     CharacterDevice,
+
+    /// The file descriptor or file refers to a directory inode.
     Directory,
+
+    /// The file descriptor or file refers to a regular file inode.
     RegularFile,
+
+    /// The file descriptor or file refers to a datagram socket.
     SocketDgram,
+
+    /// The file descriptor or file refers to a byte-stream socket.
     SocketStream,
+
+    /// The file refers to a symbolic link inode.
     SymbolicLink,
 }
 
@@ -645,6 +1162,43 @@ impl From<FileType> for u8 {
     }
 }
 
+impl FileType {
+    /// This file type's short identifier, e.g. `"RegularFile"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            FileType::Unknown => "Unknown",
+            FileType::BlockDevice => "BlockDevice",
+            FileType::CharacterDevice => "CharacterDevice",
+            FileType::Directory => "Directory",
+            FileType::RegularFile => "RegularFile",
+            FileType::SocketDgram => "SocketDgram",
+            FileType::SocketStream => "SocketStream",
+            FileType::SymbolicLink => "SymbolicLink",
+        }
+    }
+
+    /// A human-readable description of this file type.
+    #[inline]
+    pub const fn message(&self) -> &'static str {
+        match self {
+            FileType::Unknown => {
+                "The type of the file descriptor or file is unknown or is different from any of \
+                 the other types specified."
+            }
+            FileType::BlockDevice => "The file descriptor or file refers to a block device inode.",
+            FileType::CharacterDevice => {
+                "The file descriptor or file refers to a character device inode."
+            }
+            FileType::Directory => "The file descriptor or file refers to a directory inode.",
+            FileType::RegularFile => "The file descriptor or file refers to a regular file inode.",
+            FileType::SocketDgram => "The file descriptor or file refers to a datagram socket.",
+            FileType::SocketStream => "The file descriptor or file refers to a byte-stream socket.",
+            FileType::SymbolicLink => "The file refers to a symbolic link inode.",
+        }
+    }
+}
+
 pub type FileSize = u64;
 
 /// File attributes.
@@ -689,7 +1243,7 @@ pub struct IoVec {
 }
 
 /// Number of hard links to an inode.
-pub type LinkCount = u32;
+pub type LinkCount = u64;
 
 bitflags! {
     #[derive(Default)]
@@ -782,32 +1336,85 @@ impl From<Rights> for u64 {
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Prim)]
 pub enum Signal {
+    /// No signal. Reserved.
     Reserved,
+
+    /// Process abort signal.
     Abort,
+
+    /// Alarm clock.
     Alarm,
+
+    /// Access to an undefined portion of a memory object.
     Bus,
+
+    /// Child process terminated, stopped, or continued.
     Child,
+
+    /// Continue executing, if stopped.
     Cont,
+
+    /// Erroneous arithmetic operation.
     FP,
+
+    /// Hangup.
     Hup,
+
+    /// Illegal instruction.
     Ill,
+
+    /// Terminate interrupt signal.
     Int,
+
+    /// Kill.
     Kill,
+
+    /// Write on a pipe with no one to read it.
     Pipe,
+
+    /// Terminal quit signal.
     Quit,
+
+    /// Invalid memory reference.
     Seg,
+
+    /// Stop executing.
     Stop,
+
+    /// Bad system call.
     Sys,
+
+    /// Termination signal.
     Term,
+
+    /// Trace and breakpoint trap.
     Trap,
+
+    /// Terminal stop signal.
     TStp,
+
+    /// Background process attempting read.
     TTIn,
+
+    /// Background process attempting write.
     TTOut,
+
+    /// High bandwidth data is available at a socket.
     Urg,
+
+    /// User-defined signal 1.
     Usr1,
+
+    /// User-defined signal 2.
     Usr2,
+
+    /// Virtual timer expired.
     VTAlrm,
+
+    /// CPU time limit exceeded.
     XCpu,
+
+    /// File size limit exceeded.
     XFSz,
 }
 
@@ -818,6 +1425,76 @@ impl From<Signal> for u8 {
     }
 }
 
+impl Signal {
+    /// This signal's short identifier, e.g. `"Kill"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Signal::Reserved => "Reserved",
+            Signal::Abort => "Abort",
+            Signal::Alarm => "Alarm",
+            Signal::Bus => "Bus",
+            Signal::Child => "Child",
+            Signal::Cont => "Cont",
+            Signal::FP => "FP",
+            Signal::Hup => "Hup",
+            Signal::Ill => "Ill",
+            Signal::Int => "Int",
+            Signal::Kill => "Kill",
+            Signal::Pipe => "Pipe",
+            Signal::Quit => "Quit",
+            Signal::Seg => "Seg",
+            Signal::Stop => "Stop",
+            Signal::Sys => "Sys",
+            Signal::Term => "Term",
+            Signal::Trap => "Trap",
+            Signal::TStp => "TStp",
+            Signal::TTIn => "TTIn",
+            Signal::TTOut => "TTOut",
+            Signal::Urg => "Urg",
+            Signal::Usr1 => "Usr1",
+            Signal::Usr2 => "Usr2",
+            Signal::VTAlrm => "VTAlrm",
+            Signal::XCpu => "XCpu",
+            Signal::XFSz => "XFSz",
+        }
+    }
+
+    /// A human-readable description of this signal.
+    #[inline]
+    pub const fn message(&self) -> &'static str {
+        match self {
+            Signal::Reserved => "No signal. Reserved.",
+            Signal::Abort => "Process abort signal.",
+            Signal::Alarm => "Alarm clock.",
+            Signal::Bus => "Access to an undefined portion of a memory object.",
+            Signal::Child => "Child process terminated, stopped, or continued.",
+            Signal::Cont => "Continue executing, if stopped.",
+            Signal::FP => "Erroneous arithmetic operation.",
+            Signal::Hup => "Hangup.",
+            Signal::Ill => "Illegal instruction.",
+            Signal::Int => "Terminate interrupt signal.",
+            Signal::Kill => "Kill.",
+            Signal::Pipe => "Write on a pipe with no one to read it.",
+            Signal::Quit => "Terminal quit signal.",
+            Signal::Seg => "Invalid memory reference.",
+            Signal::Stop => "Stop executing.",
+            Signal::Sys => "Bad system call.",
+            Signal::Term => "Termination signal.",
+            Signal::Trap => "Trace and breakpoint trap.",
+            Signal::TStp => "Terminal stop signal.",
+            Signal::TTIn => "Background process attempting read.",
+            Signal::TTOut => "Background process attempting write.",
+            Signal::Urg => "High bandwidth data is available at a socket.",
+            Signal::Usr1 => "User-defined signal 1.",
+            Signal::Usr2 => "User-defined signal 2.",
+            Signal::VTAlrm => "Virtual timer expired.",
+            Signal::XCpu => "CPU time limit exceeded.",
+            Signal::XFSz => "File size limit exceeded.",
+        }
+    }
+}
+
 /// Timestamp in nanoseconds.
 #[derive(Prim, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Timestamp(u64);
@@ -867,8 +1544,13 @@ pub type UserData = u64;
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Prim)]
 pub enum Whence {
+    /// Seek relative to start-of-file.
     Start,
+
+    /// Seek relative to current position.
     Current,
+
+    /// Seek relative to end-of-file.
     End,
 }
 
@@ -879,6 +1561,28 @@ impl From<Whence> for u8 {
     }
 }
 
+impl Whence {
+    /// This whence's short identifier, e.g. `"Current"`.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Whence::Start => "Start",
+            Whence::Current => "Current",
+            Whence::End => "End",
+        }
+    }
+
+    /// A human-readable description of this whence.
+    #[inline]
+    pub const fn message(&self) -> &'static str {
+        match self {
+            Whence::Start => "Seek relative to start-of-file.",
+            Whence::Current => "Seek relative to current position.",
+            Whence::End => "Seek relative to end-of-file.",
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
 pub struct Subscription {
@@ -1012,3 +1716,78 @@ impl From<RoFlags> for u16 {
         flags.bits
     }
 }
+
+/// The protocol family of a socket address, following the CloudABI socket model WASI
+/// descends from.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Prim)]
+pub enum AddressFamily {
+    /// IPv4.
+    Inet4,
+
+    /// IPv6.
+    Inet6,
+
+    /// Unix domain socket.
+    Unix,
+}
+
+impl From<AddressFamily> for u8 {
+    #[inline]
+    fn from(family: AddressFamily) -> Self {
+        family as u8
+    }
+}
+
+/// The communication semantics of a socket.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Prim)]
+pub enum SockType {
+    /// A stream socket.
+    Stream,
+
+    /// A datagram socket.
+    Datagram,
+}
+
+impl From<SockType> for u8 {
+    #[inline]
+    fn from(ty: SockType) -> Self {
+        ty as u8
+    }
+}
+
+/// An IPv4 socket address.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SockAddrIn4 {
+    pub addr: [u8; 4],
+    pub port: u16,
+}
+
+/// An IPv6 socket address.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SockAddrIn6 {
+    pub addr: [u8; 16],
+    pub port: u16,
+}
+
+/// A socket address, tagged by its `AddressFamily`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SockAddr {
+    Inet4(SockAddrIn4),
+    Inet6(SockAddrIn6),
+    Unix,
+}
+
+impl SockAddr {
+    /// The address family of this address.
+    pub fn family(&self) -> AddressFamily {
+        match self {
+            SockAddr::Inet4(_) => AddressFamily::Inet4,
+            SockAddr::Inet6(_) => AddressFamily::Inet6,
+            SockAddr::Unix => AddressFamily::Unix,
+        }
+    }
+}